@@ -2,6 +2,8 @@ use csv::Reader;
 use petgraph::algo::{connected_components, dijkstra};
 use petgraph::graph::{UnGraph, NodeIndex};
 use petgraph::visit::IntoNodeIdentifiers;
+use rand::seq::SliceRandom;
+use rand::Rng;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::error::Error;
@@ -24,7 +26,72 @@ fn read_data(path: &str) -> Result<Vec<Player>, Box<dyn Error>> {
     Ok(players)
 }
 
-fn create_graph(players: &[Player]) -> UnGraph<String, &'static str> {
+// Union-Find over player nodes
+struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(n: usize) -> Self {
+        DisjointSet {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+        }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    fn join(&mut self, x: usize, y: usize) {
+        let root_x = self.find(x);
+        let root_y = self.find(y);
+        if root_x == root_y {
+            return;
+        }
+
+        let (small, large) = if self.rank[root_x] < self.rank[root_y] {
+            (root_x, root_y)
+        } else {
+            (root_y, root_x)
+        };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        if self.rank[root_x] == self.rank[root_y] {
+            self.rank[large] += 1;
+        }
+    }
+
+    // Did these two players ever share a roster chain?
+    fn connected(&mut self, a: NodeIndex, b: NodeIndex) -> bool {
+        self.find(a.index()) == self.find(b.index())
+    }
+
+    // Number of players in each cluster, keyed by root index.
+    fn component_sizes(&self) -> HashMap<usize, usize> {
+        let mut sizes = HashMap::new();
+        let mut roots = DisjointSet {
+            parent: self.parent.clone(),
+            rank: self.rank.clone(),
+            size: self.size.clone(),
+        };
+        for i in 0..roots.parent.len() {
+            let root = roots.find(i);
+            sizes.insert(root, roots.size[root]);
+        }
+        sizes
+    }
+}
+
+// Build the teammate graph, weighting each edge by shared team count, and
+// a DisjointSet over the same nodes.
+fn create_graph(players: &[Player]) -> (UnGraph<String, u32>, DisjointSet) {
     let mut graph = UnGraph::new_undirected();
     let mut indices: HashMap<String, NodeIndex> = HashMap::new();
 
@@ -43,34 +110,236 @@ fn create_graph(players: &[Player]) -> UnGraph<String, &'static str> {
             .push(node);
     }
 
+    let mut disjoint_set = DisjointSet::new(graph.node_count());
+
     for teammates in team_map.values() {
         for (i, &teammate1) in teammates.iter().enumerate() {
             for &teammate2 in &teammates[i + 1..] {
-                graph.add_edge(teammate1, teammate2, "teammate");
+                if let Some(edge) = graph.find_edge(teammate1, teammate2) {
+                    graph[edge] += 1;
+                } else {
+                    graph.add_edge(teammate1, teammate2, 1);
+                }
+                disjoint_set.join(teammate1.index(), teammate2.index());
             }
         }
     }
 
-    graph
+    (graph, disjoint_set)
 }
 
 // Find connected components in the graph
-fn find_connected_components(graph: &UnGraph<String, &'static str>) -> usize {
+fn find_connected_components(graph: &UnGraph<String, u32>) -> usize {
     connected_components(graph)
 }
 
-// Compute closeness centrality for each node
-fn compute_closeness_centrality(graph: &UnGraph<String, &'static str>) -> HashMap<NodeIndex, f64> {
+// Split the graph into one standalone UnGraph per connected component
+fn decompose_connected_components(
+    graph: &UnGraph<String, u32>,
+) -> Vec<UnGraph<String, u32>> {
+    let mut visited = vec![false; graph.node_count()];
+    let mut components = Vec::new();
+
+    for start in graph.node_identifiers() {
+        if visited[start.index()] {
+            continue;
+        }
+
+        let mut component = UnGraph::new_undirected();
+        let mut old_to_new: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+        let mut queue = std::collections::VecDeque::new();
+
+        visited[start.index()] = true;
+        queue.push_back(start);
+        let new_start = component.add_node(graph[start].clone());
+        old_to_new.insert(start, new_start);
+
+        while let Some(old_node) = queue.pop_front() {
+            for neighbor in graph.neighbors(old_node) {
+                if !visited[neighbor.index()] {
+                    visited[neighbor.index()] = true;
+                    let new_neighbor = component.add_node(graph[neighbor].clone());
+                    old_to_new.insert(neighbor, new_neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+
+            for neighbor in graph.neighbors(old_node) {
+                let new_old = old_to_new[&old_node];
+                let new_neighbor = old_to_new[&neighbor];
+                if !component.contains_edge(new_old, new_neighbor) {
+                    let weight = graph[graph.find_edge(old_node, neighbor).unwrap()];
+                    component.add_edge(new_old, new_neighbor, weight);
+                }
+            }
+        }
+
+        components.push(component);
+    }
+
+    components
+}
+
+// Detect communities via asynchronous label propagation (each node adopts
+// its neighbors' plurality label in place, so later nodes in the same pass
+// see already-updated labels).
+fn detect_communities(graph: &UnGraph<String, u32>) -> HashMap<NodeIndex, usize> {
+    let max_passes = 20;
+    let mut rng = rand::thread_rng();
+
+    let mut labels: HashMap<NodeIndex, usize> = graph
+        .node_identifiers()
+        .map(|node| (node, node.index()))
+        .collect();
+
+    let mut order: Vec<NodeIndex> = graph.node_identifiers().collect();
+
+    for _ in 0..max_passes {
+        order.shuffle(&mut rng);
+        let mut changed = false;
+
+        for &node in &order {
+            let mut counts: HashMap<usize, usize> = HashMap::new();
+            for neighbor in graph.neighbors(node) {
+                *counts.entry(labels[&neighbor]).or_insert(0) += 1;
+            }
+            if counts.is_empty() {
+                continue;
+            }
+
+            let max_count = *counts.values().max().unwrap();
+            let mut candidates: Vec<usize> = counts
+                .into_iter()
+                .filter(|&(_, count)| count == max_count)
+                .map(|(label, _)| label)
+                .collect();
+            candidates.sort_unstable();
+            let chosen = if candidates.len() == 1 {
+                candidates[0]
+            } else {
+                candidates[rng.gen_range(0..candidates.len())]
+            };
+
+            if labels[&node] != chosen {
+                labels.insert(node, chosen);
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let communities: std::collections::HashSet<usize> = labels.values().copied().collect();
+    println!("Number of communities detected: {}", communities.len());
+
+    labels
+}
+
+// Compute betweenness centrality for each node using Brandes' algorithm
+fn compute_betweenness_centrality(
+    graph: &UnGraph<String, u32>,
+    normalized: bool,
+) -> HashMap<NodeIndex, f64> {
+    let mut betweenness: HashMap<NodeIndex, f64> = graph
+        .node_identifiers()
+        .map(|node| (node, 0.0))
+        .collect();
+
+    for s in graph.node_identifiers() {
+        let mut stack = Vec::new();
+        let mut pred: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+        let mut sigma: HashMap<NodeIndex, f64> = HashMap::new();
+        let mut dist: HashMap<NodeIndex, i64> = HashMap::new();
+
+        for node in graph.node_identifiers() {
+            pred.insert(node, Vec::new());
+            sigma.insert(node, 0.0);
+            dist.insert(node, -1);
+        }
+        sigma.insert(s, 1.0);
+        dist.insert(s, 0);
+
+        let mut queue = std::collections::VecDeque::new();
+        queue.push_back(s);
+
+        while let Some(v) = queue.pop_front() {
+            stack.push(v);
+            for w in graph.neighbors(v) {
+                if dist[&w] < 0 {
+                    dist.insert(w, dist[&v] + 1);
+                    queue.push_back(w);
+                }
+                if dist[&w] == dist[&v] + 1 {
+                    let sigma_v = sigma[&v];
+                    *sigma.get_mut(&w).unwrap() += sigma_v;
+                    pred.get_mut(&w).unwrap().push(v);
+                }
+            }
+        }
+
+        let mut delta: HashMap<NodeIndex, f64> = graph
+            .node_identifiers()
+            .map(|node| (node, 0.0))
+            .collect();
+
+        while let Some(w) = stack.pop() {
+            for &v in &pred[&w] {
+                let contrib = (sigma[&v] / sigma[&w]) * (1.0 + delta[&w]);
+                *delta.get_mut(&v).unwrap() += contrib;
+            }
+            if w != s {
+                *betweenness.get_mut(&w).unwrap() += delta[&w];
+            }
+        }
+    }
+
+    // Graph is undirected, so every shortest path was counted from both endpoints
+    for value in betweenness.values_mut() {
+        *value /= 2.0;
+    }
+
+    if normalized {
+        let n = graph.node_count() as f64;
+        let scale = (n - 1.0) * (n - 2.0) / 2.0;
+        if scale > 0.0 {
+            for value in betweenness.values_mut() {
+                *value /= scale;
+            }
+        }
+    }
+
+    betweenness
+}
+
+// Compute closeness centrality for each node, optionally Wasserman-Faust
+// normalized and/or weighted by inverse edge weight.
+fn compute_closeness_centrality(
+    graph: &UnGraph<String, u32>,
+    global_normalize: bool,
+    weighted: bool,
+) -> HashMap<NodeIndex, f64> {
     let mut centrality = HashMap::new();
     let node_count = graph.node_count() as f64;
 
     for node in graph.node_identifiers() {
-        let path_lengths = dijkstra(graph, node, None, |_| 1);
-        let total_path_length: usize = path_lengths.values().map(|&d| d).sum();
-        let closeness = if total_path_length > 0 {
-            (node_count - 1.0) / total_path_length as f64
+        let path_lengths = if weighted {
+            dijkstra(graph, node, None, |e| 1.0 / (*e.weight() as f64).max(1.0))
         } else {
+            dijkstra(graph, node, None, |_| 1.0)
+        };
+        // dijkstra includes the source itself (distance 0), so `r` must
+        // exclude it to match "reachable nodes other than the source".
+        let reachable = path_lengths.len() as f64 - 1.0;
+        let total_path_length: f64 = path_lengths.values().sum();
+
+        let closeness = if total_path_length == 0.0 || node_count <= 1.0 {
             0.0
+        } else if global_normalize {
+            (reachable * reachable) / (total_path_length * (node_count - 1.0))
+        } else {
+            reachable / total_path_length
         };
         centrality.insert(node, closeness);
     }
@@ -80,17 +349,141 @@ fn compute_closeness_centrality(graph: &UnGraph<String, &'static str>) -> HashMa
 
 fn main() -> Result<(), Box<dyn Error>> {
     let players = read_data("nba.csv")?;
-    let graph = create_graph(&players);
+    let (graph, mut disjoint_set) = create_graph(&players);
 
     let num_components = find_connected_components(&graph);
     println!("Number of connected components: {}", num_components);
 
-    let centrality = compute_closeness_centrality(&graph);
+    let sizes = disjoint_set.component_sizes();
+    println!("Cluster sizes (via union-find): {} clusters", sizes.len());
+    for (root, size) in &sizes {
+        println!("Cluster rooted at {}: {} players", root, size);
+    }
+
+    if let (Some(first), Some(second)) = (graph.node_indices().next(), graph.node_indices().nth(1)) {
+        println!(
+            "{} and {} share a roster chain: {}",
+            graph[first],
+            graph[second],
+            disjoint_set.connected(first, second)
+        );
+    }
+
+    let components = decompose_connected_components(&graph);
+    for (i, component) in components.iter().enumerate() {
+        println!(
+            "Component {}: {} players, {} teammate links",
+            i,
+            component.node_count(),
+            component.edge_count()
+        );
+    }
+
+    let centrality = compute_closeness_centrality(&graph, true, true);
     for (node, value) in centrality {
         println!("Node {}: Closeness Centrality = {}", graph[node], value);
     }
 
+    let betweenness = compute_betweenness_centrality(&graph, false);
+    for (node, value) in betweenness {
+        println!("Node {}: Betweenness Centrality = {}", graph[node], value);
+    }
+
+    let communities = detect_communities(&graph);
+    for (node, community) in communities {
+        println!("Node {}: Community = {}", graph[node], community);
+    }
+
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn betweenness_star_graph_peaks_at_center() {
+        let mut graph: UnGraph<String, u32> = UnGraph::new_undirected();
+        let center = graph.add_node("center".to_string());
+        let leaves: Vec<NodeIndex> = (0..3)
+            .map(|i| graph.add_node(format!("leaf{}", i)))
+            .collect();
+        for &leaf in &leaves {
+            graph.add_edge(center, leaf, 1);
+        }
+
+        let betweenness = compute_betweenness_centrality(&graph, false);
+        assert_eq!(betweenness[&center], 3.0);
+        for &leaf in &leaves {
+            assert_eq!(betweenness[&leaf], 0.0);
+        }
+    }
+
+    #[test]
+    fn betweenness_path_graph_favors_middle_nodes() {
+        let mut graph: UnGraph<String, u32> = UnGraph::new_undirected();
+        let nodes: Vec<NodeIndex> = (0..4).map(|i| graph.add_node(format!("n{}", i))).collect();
+        graph.add_edge(nodes[0], nodes[1], 1);
+        graph.add_edge(nodes[1], nodes[2], 1);
+        graph.add_edge(nodes[2], nodes[3], 1);
+
+        let betweenness = compute_betweenness_centrality(&graph, false);
+        assert_eq!(betweenness[&nodes[0]], 0.0);
+        assert_eq!(betweenness[&nodes[1]], 2.0);
+        assert_eq!(betweenness[&nodes[2]], 2.0);
+        assert_eq!(betweenness[&nodes[3]], 0.0);
+    }
+
+    #[test]
+    fn closeness_wasserman_faust_normalizes_across_components() {
+        let mut graph: UnGraph<String, u32> = UnGraph::new_undirected();
+        let a = graph.add_node("a".to_string());
+        let b = graph.add_node("b".to_string());
+        let c = graph.add_node("c".to_string());
+        let _d = graph.add_node("d".to_string());
+        graph.add_edge(a, b, 1);
+
+        let raw = compute_closeness_centrality(&graph, false, false);
+        let wf = compute_closeness_centrality(&graph, true, false);
+
+        // a is directly connected to its only reachable peer, so raw closeness is 1.0
+        assert_eq!(raw[&a], 1.0);
+        // Wasserman-Faust scales that down by how much of the whole graph is reachable (1 of 3 others)
+        assert!((wf[&a] - (1.0 / 3.0)).abs() < 1e-9);
+        // c is isolated, so both forms report 0.0 rather than an inflated component-local score
+        assert_eq!(raw[&c], 0.0);
+        assert_eq!(wf[&c], 0.0);
+    }
+
+    #[test]
+    fn communities_never_cross_disconnected_components() {
+        let mut graph: UnGraph<String, u32> = UnGraph::new_undirected();
+        let a = graph.add_node("a".to_string());
+        let b = graph.add_node("b".to_string());
+        let c = graph.add_node("c".to_string());
+        let d = graph.add_node("d".to_string());
+        graph.add_edge(a, b, 1);
+        graph.add_edge(c, d, 1);
+
+        let communities = detect_communities(&graph);
+        assert_eq!(communities[&a], communities[&b]);
+        assert_eq!(communities[&c], communities[&d]);
+        assert_ne!(communities[&a], communities[&c]);
+    }
+
+    #[test]
+    fn disjoint_set_tracks_connectivity_and_cluster_sizes() {
+        let mut ds = DisjointSet::new(5);
+        ds.join(0, 1);
+        ds.join(1, 2);
+        ds.join(3, 4);
+
+        assert!(ds.connected(NodeIndex::new(0), NodeIndex::new(2)));
+        assert!(!ds.connected(NodeIndex::new(0), NodeIndex::new(3)));
+
+        let mut sizes: Vec<usize> = ds.component_sizes().values().copied().collect();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![2, 3]);
+    }
+}
 